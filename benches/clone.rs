@@ -0,0 +1,82 @@
+//! Compares the cost of sharing a boxed value for the first time.
+//!
+//! Run with `cargo bench --bench clone` and again with `--features counter-pool` to compare the
+//! global allocator path against the thread-local counter pool.
+
+use std::hint::black_box;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use lazyrc::{LazyRc, ThinLazyRc};
+
+const ITERS: u32 = 1_000_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up allocator caches (and the counter pool, if enabled).
+    for _ in 0..ITERS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        f();
+    }
+    let per_iter: Duration = start.elapsed() / ITERS;
+    println!("{name:<32} {per_iter:>10.2?}/iter");
+}
+
+fn main() {
+    let pool = if cfg!(feature = "counter-pool") {
+        "pooled counter"
+    } else {
+        "boxed counter"
+    };
+    println!("LazyRc with {pool}");
+
+    bench("LazyRc: new + clone + drop", || {
+        let v = LazyRc::new(black_box(Box::new([0u64; 8])));
+        let w = v.clone();
+        drop(black_box(w));
+        drop(black_box(v));
+    });
+
+    bench("ThinLazyRc: new + clone + drop", || {
+        let v = ThinLazyRc::new(black_box(Box::new([0u64; 8])));
+        let w = v.clone();
+        drop(black_box(w));
+        drop(black_box(v));
+    });
+
+    bench("Rc: new + clone + drop", || {
+        let v = Rc::new(black_box([0u64; 8]));
+        let w = v.clone();
+        drop(black_box(w));
+        drop(black_box(v));
+    });
+
+    bench("Rc: from box + clone + drop", || {
+        let v: Rc<[u64; 8]> = Rc::from(black_box(Box::new([0u64; 8])));
+        let w = v.clone();
+        drop(black_box(w));
+        drop(black_box(v));
+    });
+
+    // Reading through a vector of shared handles, where `ThinLazyRc` is half the size but goes
+    // through its shared block to reach the value.
+    let values: Vec<u64> = (0..1024).collect();
+    let lazy: Vec<LazyRc<u64>> = values.iter().map(|&v| LazyRc::from(v)).collect();
+    let lazy_peers = lazy.clone();
+    let thin: Vec<ThinLazyRc<u64>> = values.iter().map(|&v| ThinLazyRc::from(v)).collect();
+    let thin_peers = thin.clone();
+    let rc: Vec<Rc<u64>> = values.iter().map(|&v| Rc::new(v)).collect();
+
+    bench("LazyRc: sum 1024 shared", || {
+        black_box(black_box(&lazy).iter().map(|v| **v).sum::<u64>());
+    });
+    bench("ThinLazyRc: sum 1024 shared", || {
+        black_box(black_box(&thin).iter().map(|v| **v).sum::<u64>());
+    });
+    bench("Rc: sum 1024", || {
+        black_box(black_box(&rc).iter().map(|v| **v).sum::<u64>());
+    });
+    drop((lazy_peers, thin_peers));
+}
@@ -0,0 +1,112 @@
+//! Measures conversions between `LazyRc` and `std`'s `Rc`/`Arc`, and how many allocations each
+//! makes.
+//!
+//! Run with `cargo bench --bench convert`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use lazyrc::LazyRc;
+
+const ITERS: u32 = 100_000;
+
+/// Counts allocations made through the system allocator.
+struct Counting;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Times the conversion only; `setup` builds its input, and allocations made there aren't counted.
+fn bench<I, O>(name: &str, mut setup: impl FnMut() -> I, mut convert: impl FnMut(I) -> O) {
+    let mut elapsed = Duration::ZERO;
+    let mut allocs = 0;
+    for _ in 0..ITERS {
+        let input = black_box(setup());
+        let before = ALLOCS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let output = black_box(convert(input));
+        elapsed += start.elapsed();
+        allocs += ALLOCS.load(Ordering::Relaxed) - before;
+        drop(output);
+    }
+    let per_iter = elapsed / ITERS;
+    let allocs = allocs as f64 / f64::from(ITERS);
+    println!("{name:<40} {per_iter:>10.2?}/iter {allocs:>5.1} allocs");
+}
+
+fn main() {
+    const LEN: usize = 4096;
+
+    bench(
+        "LazyRc<[u8]> -> Rc<[u8]>, unshared",
+        || LazyRc::<[u8]>::from(vec![0; LEN]),
+        Rc::<[u8]>::from,
+    );
+    bench(
+        "LazyRc<[u8]> -> Rc<[u8]>, shared",
+        || {
+            let lazy = LazyRc::<[u8]>::from(vec![0; LEN]);
+            (lazy.clone(), lazy)
+        },
+        // Keep the peer alive until after the conversion.
+        |(lazy, peer)| (Rc::<[u8]>::from(lazy), peer),
+    );
+    bench(
+        "LazyRc<str> -> Arc<str>",
+        || LazyRc::<str>::from("x".repeat(LEN)),
+        Arc::<str>::from,
+    );
+    bench(
+        "LazyRc<Vec<u8>> -> Rc<Vec<u8>>, unshared",
+        || LazyRc::new(Box::new(vec![0u8; LEN])),
+        Rc::<Vec<u8>>::from,
+    );
+    bench(
+        "LazyRc<Vec<u8>> -> Rc<Vec<u8>>, shared",
+        || {
+            let lazy = LazyRc::new(Box::new(vec![0u8; LEN]));
+            (lazy.clone(), lazy)
+        },
+        |(lazy, peer)| (Rc::<Vec<u8>>::from(lazy), peer),
+    );
+    bench(
+        "Rc<Vec<u8>> -> LazyRc<Vec<u8>>, unshared",
+        || Rc::new(vec![0u8; LEN]),
+        LazyRc::<Vec<u8>>::from,
+    );
+    bench(
+        "Rc<Vec<u8>> -> LazyRc<Vec<u8>>, shared",
+        || {
+            let rc = Rc::new(vec![0u8; LEN]);
+            (rc.clone(), rc)
+        },
+        |(rc, peer)| (LazyRc::<Vec<u8>>::from(rc), peer),
+    );
+    bench(
+        "Box<[u8]> -> LazyRc<[u8]>",
+        || vec![0u8; LEN].into_boxed_slice(),
+        LazyRc::<[u8]>::from,
+    );
+    bench(
+        "Box<[u8]> -> Rc<[u8]>",
+        || vec![0u8; LEN].into_boxed_slice(),
+        Rc::<[u8]>::from,
+    );
+}
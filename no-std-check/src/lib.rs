@@ -0,0 +1,23 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use lazyrc::{LazyArc, LazyRc};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+pub fn share() -> usize {
+    let rc: LazyRc<[u8]> = LazyRc::from(vec![1, 2, 3]);
+    let peer = rc.clone();
+    let arc = LazyArc::new(Box::new(LazyRc::strong_count(&peer)));
+    let _ = arc.clone();
+    *arc + rc.len()
+}
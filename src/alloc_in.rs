@@ -0,0 +1,180 @@
+use core::cell::Cell;
+use core::fmt::{self, Debug, Display};
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::boxed::Box;
+
+use crate::increment;
+
+/// A [`LazyRc`](crate::LazyRc) backed by a custom allocator.
+///
+/// The value and (on first clone) the share count are both allocated from `A`, and freed through
+/// it. Every handle carries its own copy of the allocator, so cloning requires `A: Clone`.
+pub struct LazyRcIn<T: ?Sized, A: Allocator = Global> {
+    data: NonNull<T>,
+    share_count: Cell<*const Cell<usize>>,
+    alloc: A,
+}
+
+impl<T: ?Sized, A: Allocator> Debug for LazyRcIn<T, A>
+where
+    T: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Display for LazyRcIn<T, A>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Deref for LazyRcIn<T, A> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> LazyRcIn<T, A> {
+    #[inline]
+    pub fn new(inner: Box<T, A>) -> Self {
+        let (data, alloc) = Box::into_raw_with_allocator(inner);
+        LazyRcIn {
+            // Box always returns a non-null pointer.
+            data: unsafe { NonNull::new_unchecked(data) },
+            share_count: Cell::new(ptr::null()),
+            alloc,
+        }
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    pub fn allocator(this: &Self) -> &A {
+        &this.alloc
+    }
+
+    /// Gets the number of `LazyRcIn` pointers to this allocation.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        unsafe { this.share_count.get().as_ref() }.map_or(1, Cell::get)
+    }
+
+    /// Returns the inner box if this is the only reference, freeing the share count (if any).
+    /// Otherwise, the `LazyRcIn` is returned unchanged.
+    pub fn try_into_box(this: Self) -> Result<Box<T, A>, Self> {
+        let counter = this.share_count.get();
+        unsafe {
+            if let Some(count) = counter.as_ref() {
+                if count.get() != 1 {
+                    return Err(this);
+                }
+                drop(Box::from_raw_in(counter as *mut Cell<usize>, &this.alloc));
+            }
+            let this = ManuallyDrop::new(this);
+            let alloc = ptr::read(&this.alloc);
+            Ok(Box::from_raw_in(this.data.as_ptr(), alloc))
+        }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> From<Box<T, A>> for LazyRcIn<T, A> {
+    fn from(value: Box<T, A>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Clone> Clone for LazyRcIn<T, A> {
+    fn clone(&self) -> Self {
+        unsafe {
+            if let Some(counter) = self.share_count.get().as_ref() {
+                increment(counter);
+            } else {
+                let counter = Box::new_in(Cell::new(2usize), &self.alloc);
+                self.share_count.set(Box::into_raw(counter));
+            }
+        }
+
+        Self {
+            data: self.data,
+            share_count: self.share_count.clone(),
+            alloc: self.alloc.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for LazyRcIn<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let counter = self.share_count.get();
+            if let Some(count) = counter.as_ref() {
+                if count.get() > 1 {
+                    count.set(count.get() - 1);
+                    // Nothing to deallocate.
+                    return;
+                }
+                // And drop the counter.
+                drop(Box::from_raw_in(counter as *mut Cell<usize>, &self.alloc));
+            }
+            drop(Box::from_raw_in(self.data.as_ptr(), &self.alloc));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::alloc::Layout;
+    use std::rc::Rc;
+
+    use allocator_api2::alloc::AllocError;
+
+    use super::*;
+
+    /// Tracks the number of live allocations made through it.
+    #[derive(Clone, Default)]
+    struct Counting(Rc<Cell<isize>>);
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn test_allocator() {
+        let alloc = Counting::default();
+        let mut vec = allocator_api2::vec::Vec::new_in(alloc.clone());
+        vec.extend([1u32, 2, 3]);
+        let rc = LazyRcIn::new(vec.into_boxed_slice());
+        assert_eq!(alloc.0.get(), 1);
+
+        let peer = rc.clone();
+        assert_eq!(alloc.0.get(), 2);
+        assert_eq!(*peer, [1, 2, 3]);
+
+        let rc = LazyRcIn::try_into_box(rc).unwrap_err();
+        drop(peer);
+        let boxed = LazyRcIn::try_into_box(rc).unwrap();
+        assert_eq!(alloc.0.get(), 1);
+        drop(boxed);
+        assert_eq!(alloc.0.get(), 0);
+    }
+}
@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+use bytes::{Buf, Bytes};
+
+use crate::LazyRc;
+
+/// Zero-copy if the buffer isn't shared. A shared `LazyRc` can't cross threads, so in that case
+/// the contents are copied into a new `Bytes`.
+impl From<LazyRc<[u8]>> for Bytes {
+    fn from(value: LazyRc<[u8]>) -> Self {
+        match LazyRc::try_into_box(value) {
+            Ok(boxed) => Bytes::from(boxed),
+            Err(shared) => Bytes::copy_from_slice(&shared),
+        }
+    }
+}
+
+/// Zero-copy if the `Bytes` uniquely owns its whole (exactly sized) buffer, otherwise copies.
+impl From<Bytes> for LazyRc<[u8]> {
+    fn from(value: Bytes) -> Self {
+        LazyRc::from(Vec::from(value))
+    }
+}
+
+/// A [`Buf`] reading through a [`LazyRc<[u8]>`](LazyRc).
+#[derive(Debug, Clone)]
+pub struct LazyRcCursor {
+    inner: LazyRc<[u8]>,
+    pos: usize,
+}
+
+impl LazyRcCursor {
+    #[inline]
+    pub fn new(inner: LazyRc<[u8]>) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Returns the number of bytes already read.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying buffer.
+    #[inline]
+    pub fn get_ref(&self) -> &LazyRc<[u8]> {
+        &self.inner
+    }
+
+    /// Returns the underlying buffer.
+    #[inline]
+    pub fn into_inner(self) -> LazyRc<[u8]> {
+        self.inner
+    }
+}
+
+impl From<LazyRc<[u8]>> for LazyRcCursor {
+    fn from(value: LazyRc<[u8]>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Buf for LazyRcCursor {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.len() - self.pos
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        &self.inner[self.pos..]
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining(),
+            "cannot advance past `remaining`: {} <= {}",
+            cnt,
+            self.remaining(),
+        );
+        self.pos += cnt;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let buf: LazyRc<[u8]> = LazyRc::from(vec![1, 2, 3]);
+        let ptr = buf.as_ptr();
+        let bytes = Bytes::from(buf);
+        assert_eq!(bytes.as_ptr(), ptr);
+
+        let back = LazyRc::<[u8]>::from(bytes);
+        assert_eq!(back.as_ptr(), ptr);
+
+        let peer = back.clone();
+        let copied = Bytes::from(back);
+        assert_ne!(copied.as_ptr(), ptr);
+        assert_eq!(copied, peer[..]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut cursor = LazyRcCursor::new(LazyRc::from(vec![0, 1, 0, 2, 0xff]));
+        assert_eq!(cursor.get_u16(), 1);
+        assert_eq!(cursor.get_u16_le(), 0x0200);
+        assert_eq!(cursor.remaining(), 1);
+        assert_eq!(cursor.position(), 4);
+        assert_eq!(cursor.chunk(), [0xff]);
+    }
+}
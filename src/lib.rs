@@ -1,12 +1,173 @@
-use std::fmt::{Debug, Display};
-use std::{cell::Cell, ops::Deref, ptr::NonNull};
+#![no_std]
+#![cfg_attr(feature = "unstable", feature(coerce_unsized, unsize))]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::alloc::{handle_alloc_error, Layout};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::ffi::CString;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::error::Error;
+use core::ffi::CStr;
+use core::fmt::{self, Debug, Display, Pointer};
+use core::hash::{Hash, Hasher};
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use core::pin::Pin;
+use core::{cell::Cell, ops::Deref, ptr::NonNull};
+#[cfg(feature = "unstable")]
+use core::{marker::Unsize, ops::CoerceUnsized};
+#[cfg(feature = "std")]
+use std::ffi::{OsStr, OsString};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "allocator-api2")]
+mod alloc_in;
+#[cfg(feature = "bytes")]
+mod bytes_impls;
+mod map;
+mod meta;
+#[cfg(feature = "counter-pool")]
+mod pool;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod sync;
+mod thin;
+mod unique;
+mod weak;
+
+#[cfg(feature = "allocator-api2")]
+pub use alloc_in::LazyRcIn;
+#[cfg(feature = "bytes")]
+pub use bytes_impls::LazyRcCursor;
+pub use map::MappedLazyRc;
+pub use meta::MetaLazyRc;
+pub use sync::LazyArc;
+pub use thin::ThinLazyRc;
+pub use unique::UniqueLazyRc;
+pub use weak::LazyWeak;
 
 /// A lazy ref-cell that acts like a box until cloned.
 ///
 /// Use when you have pre-boxed data that's rarely shared
 pub struct LazyRc<T: ?Sized> {
     data: NonNull<T>,
-    share_count: Cell<*const Cell<usize>>,
+    share_count: Cell<*const Counter>,
+}
+
+/// The lazily allocated counter block shared by all clones of a [`LazyRc`] and its [`LazyWeak`]s.
+///
+/// Like `std::rc::Rc`, the strong owners collectively hold a single weak reference so the block
+/// outlives the data until the last [`LazyWeak`] is dropped.
+struct Counter {
+    strong: Cell<usize>,
+    weak: Cell<usize>,
+}
+
+#[cfg(test)]
+std::thread_local! {
+    /// Makes the next counter allocation on this thread fail.
+    static FAIL_NEXT_ALLOC: Cell<bool> = const { Cell::new(false) };
+}
+
+impl Counter {
+    /// Allocates a counter block, aborting (via `handle_alloc_error`) on failure.
+    fn alloc(strong: usize, weak: usize) -> *const Counter {
+        Self::try_alloc(strong, weak).unwrap_or_else(|_| handle_alloc_error(Layout::new::<Self>()))
+    }
+
+    /// Allocates a counter block, reusing one from the thread-local pool when enabled.
+    fn try_alloc(strong: usize, weak: usize) -> Result<*const Counter, AllocError> {
+        #[cfg(test)]
+        if FAIL_NEXT_ALLOC.with(|fail| fail.replace(false)) {
+            return Err(AllocError);
+        }
+        #[cfg(feature = "counter-pool")]
+        if let Some(counter) = pool::take() {
+            counter.strong.set(strong);
+            counter.weak.set(weak);
+            return Ok(Box::into_raw(counter));
+        }
+        // Allocate by hand as `Box::try_new` isn't stable. The layout matches `Box<Counter>`, so
+        // the block can still be freed as one.
+        unsafe {
+            let counter = alloc::alloc::alloc(Layout::new::<Self>()) as *mut Counter;
+            if counter.is_null() {
+                return Err(AllocError);
+            }
+            counter.write(Counter {
+                strong: Cell::new(strong),
+                weak: Cell::new(weak),
+            });
+            Ok(counter)
+        }
+    }
+
+    /// Frees a counter block allocated with [`Counter::try_alloc`].
+    ///
+    /// # Safety
+    ///
+    /// `counter` must have come from [`Counter::try_alloc`] and must not be used afterwards.
+    unsafe fn free(counter: *const Counter) {
+        let counter = Box::from_raw(counter as *mut Counter);
+        #[cfg(feature = "counter-pool")]
+        pool::give(counter);
+        #[cfg(not(feature = "counter-pool"))]
+        drop(counter);
+    }
+}
+
+/// The error returned by [`LazyRc::try_clone`] when the counter block can't be allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl Error for AllocError {}
+
+/// Increments a reference count, aborting if it would overflow.
+///
+/// Like `Rc`, this aborts rather than panics: `mem::forget`ing clones in a loop could otherwise
+/// wrap the count and free the value while it's still referenced.
+#[inline]
+pub(crate) fn increment(count: &Cell<usize>) {
+    let count_plus_one = count.get().wrapping_add(1);
+    count.set(count_plus_one);
+    if count_plus_one == 0 {
+        abort();
+    }
+}
+
+#[cold]
+pub(crate) fn abort() -> ! {
+    #[cfg(feature = "std")]
+    std::process::abort();
+    #[cfg(not(feature = "std"))]
+    {
+        // Panicking while unwinding from a panic aborts.
+        struct Abort;
+        impl Drop for Abort {
+            fn drop(&mut self) {
+                panic!("reference count overflow");
+            }
+        }
+        let _abort = Abort;
+        panic!("reference count overflow");
+    }
 }
 
 impl<T: ?Sized> Default for LazyRc<T>
@@ -24,7 +185,7 @@ where
     T: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(&**self, f)
     }
 }
@@ -34,7 +195,7 @@ where
     T: Display,
 {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Display::fmt(&**self, f)
     }
 }
@@ -48,6 +209,66 @@ impl<T: ?Sized> Deref for LazyRc<T> {
     }
 }
 
+impl<T: ?Sized> Pointer for LazyRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Pointer::fmt(&self.data.as_ptr(), f)
+    }
+}
+
+impl<T: ?Sized> Borrow<T> for LazyRc<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for LazyRc<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for LazyRc<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for LazyRc<T> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for LazyRc<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Ord> Ord for LazyRc<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Hash> Hash for LazyRc<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: ?Sized + Error> Error for LazyRc<T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (**self).source()
+    }
+}
+
+// Like `Rc`, moving the handle never moves the value. Sharing a `&LazyRc` across an unwind isn't
+// safe (clone mutates the counter through a shared reference), but owning one is.
+impl<T: ?Sized> Unpin for LazyRc<T> {}
+impl<T: ?Sized + RefUnwindSafe> UnwindSafe for LazyRc<T> {}
+
 impl<T: ?Sized> LazyRc<T> {
     #[inline]
     pub fn new(inner: Box<T>) -> Self {
@@ -55,18 +276,405 @@ impl<T: ?Sized> LazyRc<T> {
             LazyRc {
                 // Box always returns a non-null pointer.
                 data: NonNull::new_unchecked(Box::into_raw(inner)),
-                share_count: Cell::new(std::ptr::null()),
+                share_count: Cell::new(core::ptr::null()),
+            }
+        }
+    }
+
+    /// Creates a new [`LazyWeak`] pointer to this allocation, allocating the counter block if this
+    /// `LazyRc` has never been shared.
+    pub fn downgrade(this: &Self) -> LazyWeak<T> {
+        let counter = this.counter();
+        increment(&counter.weak);
+        LazyWeak {
+            data: this.data,
+            counter: NonNull::from(counter),
+        }
+    }
+
+    /// Gets the number of `LazyRc` pointers to this allocation.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        unsafe { this.share_count.get().as_ref() }.map_or(1, |c| c.strong.get())
+    }
+
+    /// Gets the number of [`LazyWeak`] pointers to this allocation.
+    #[inline]
+    pub fn weak_count(this: &Self) -> usize {
+        unsafe { this.share_count.get().as_ref() }.map_or(0, |c| c.weak.get() - 1)
+    }
+
+    /// Returns the inner box if this is the only strong reference, without copying the value.
+    ///
+    /// Any counter block is freed (or left to the outstanding [`LazyWeak`]s, which will no longer
+    /// upgrade). Otherwise, the `LazyRc` is returned unchanged.
+    pub fn try_into_box(this: Self) -> Result<Box<T>, Self> {
+        if !this.detach() {
+            return Err(this);
+        }
+        let this = ManuallyDrop::new(this);
+        unsafe { Ok(Box::from_raw(this.data.as_ptr())) }
+    }
+
+    /// Returns a mutable reference to the value if no other `LazyRc` or [`LazyWeak`] points to it.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if this.is_unique() {
+            unsafe { Some(this.data.as_mut()) }
+        } else {
+            None
+        }
+    }
+
+    /// Pins this `LazyRc` if no other `LazyRc` or [`LazyWeak`] points to the value, otherwise
+    /// returns it unchanged.
+    ///
+    /// An unpinned peer could later move the value out (e.g. with [`LazyRc::try_into_box`]), so
+    /// only a unique `LazyRc` can be pinned after the fact.
+    pub fn into_pin(this: Self) -> Result<Pin<Self>, Self> {
+        if this.is_unique() {
+            unsafe { Ok(Pin::new_unchecked(this)) }
+        } else {
+            Err(this)
+        }
+    }
+
+    /// Returns a mutable reference to the value, copying it into a fresh box first if it's shared.
+    ///
+    /// If this is the only strong reference, outstanding [`LazyWeak`]s are disassociated (they
+    /// will no longer upgrade) instead of copying the value.
+    pub fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: ToOwned,
+        Box<T>: From<T::Owned>,
+    {
+        if !this.detach() {
+            let owned: Box<T> = (**this).to_owned().into();
+            *this = Self::new(owned);
+        }
+        unsafe { this.data.as_mut() }
+    }
+
+    /// If this is the only strong reference, disassociates it from its counter block so that it
+    /// owns the data outright again, and returns true.
+    fn detach(&self) -> bool {
+        let counter = self.share_count.get();
+        unsafe {
+            match counter.as_ref() {
+                None => true,
+                Some(c) if c.strong.get() == 1 => {
+                    c.strong.set(0);
+                    self.share_count.set(core::ptr::null());
+                    weak::release(counter);
+                    true
+                }
+                Some(_) => false,
+            }
+        }
+    }
+
+    /// Returns true if another `LazyRc` currently shares this allocation.
+    #[inline]
+    pub fn is_shared(this: &Self) -> bool {
+        Self::strong_count(this) > 1
+    }
+
+    /// Returns true if the counter block has been allocated, i.e. this `LazyRc` has been cloned or
+    /// downgraded at some point.
+    #[inline]
+    pub fn has_counter(this: &Self) -> bool {
+        !this.share_count.get().is_null()
+    }
+
+    /// Returns true if both `LazyRc`s point to the same value.
+    ///
+    /// Pointer metadata (slice lengths, vtables) is compared as well, so the usual caveats of
+    /// [`core::ptr::eq`] apply to trait objects.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        core::ptr::eq(this.data.as_ptr(), other.data.as_ptr())
+    }
+
+    /// Returns a raw pointer to the value.
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.data.as_ptr()
+    }
+
+    /// Consumes the `LazyRc`, returning the wrapped pointer and its (possibly null) counter block.
+    ///
+    /// The counter block is opaque; it is null if the `LazyRc` was never shared. To avoid a leak,
+    /// both pointers must be turned back into a `LazyRc` with [`LazyRc::from_raw`].
+    pub fn into_raw(this: Self) -> (*const T, *const ()) {
+        let this = ManuallyDrop::new(this);
+        (this.data.as_ptr(), this.share_count.get() as *const ())
+    }
+
+    /// Reconstructs a `LazyRc` from the pointers returned by [`LazyRc::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `counter` must come from a single call to [`LazyRc::into_raw`] (possibly adjusted
+    /// by [`LazyRc::increment_strong_count`]), and each such pair may only be reconstructed once
+    /// per strong reference it represents. A null `counter` means the value is unshared: there must
+    /// be no other `LazyRc` or [`LazyWeak`] pointing at it.
+    pub unsafe fn from_raw(ptr: *const T, counter: *const ()) -> Self {
+        LazyRc {
+            data: NonNull::new_unchecked(ptr as *mut T),
+            share_count: Cell::new(counter as *const Counter),
+        }
+    }
+
+    /// Increments the strong count of a value released with [`LazyRc::into_raw`].
+    ///
+    /// If the value was unshared (`*counter` is null), this allocates the counter block and
+    /// updates `*counter`; the new pair then represents two strong references.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `*counter` must satisfy the requirements of [`LazyRc::from_raw`], and every
+    /// outstanding copy of a non-null counter must still represent at least one strong reference.
+    pub unsafe fn increment_strong_count(ptr: *const T, counter: &mut *const ()) {
+        let this = ManuallyDrop::new(Self::from_raw(ptr, *counter));
+        let _clone = ManuallyDrop::new((*this).clone());
+        *counter = this.share_count.get() as *const ();
+    }
+
+    /// Decrements the strong count of a value released with [`LazyRc::into_raw`], dropping it if
+    /// this was the last strong reference.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` and `counter` must satisfy the requirements of [`LazyRc::from_raw`].
+    pub unsafe fn decrement_strong_count(ptr: *const T, counter: *const ()) {
+        drop(Self::from_raw(ptr, counter));
+    }
+
+    /// Converts to a `LazyRc<U>` pointing at the same value, e.g. a trait object, keeping the
+    /// counter block (and therefore all clones and [`LazyWeak`]s) shared.
+    ///
+    /// Prefer the safe [`unsize_lazyrc!`] macro, which only allows unsizing coercions.
+    ///
+    /// # Safety
+    ///
+    /// `cast` must return a pointer to the same value, with metadata valid for it.
+    pub unsafe fn unsize<U: ?Sized>(this: Self, cast: impl FnOnce(*mut T) -> *mut U) -> LazyRc<U> {
+        let this = ManuallyDrop::new(this);
+        LazyRc {
+            data: NonNull::new_unchecked(cast(this.data.as_ptr())),
+            share_count: Cell::new(this.share_count.get()),
+        }
+    }
+
+    /// Returns true if no other `LazyRc` or [`LazyWeak`] points to the value.
+    #[inline]
+    fn is_unique(&self) -> bool {
+        unsafe { self.share_count.get().as_ref() }
+            .is_none_or(|c| c.strong.get() == 1 && c.weak.get() == 1)
+    }
+
+    /// Returns the counter block, allocating it on first use.
+    fn counter(&self) -> &Counter {
+        unsafe {
+            if let Some(counter) = self.share_count.get().as_ref() {
+                return counter;
             }
+            let counter = Counter::alloc(1, 1);
+            self.share_count.set(counter);
+            &*counter
         }
     }
+
+    /// Like [`LazyRc::counter`], but leaves `self` untouched if the allocation fails.
+    fn try_counter(&self) -> Result<&Counter, AllocError> {
+        unsafe {
+            if let Some(counter) = self.share_count.get().as_ref() {
+                return Ok(counter);
+            }
+            let counter = Counter::try_alloc(1, 1)?;
+            self.share_count.set(counter);
+            Ok(&*counter)
+        }
+    }
+
+    /// Like `clone`, but returns an error instead of aborting if the counter block can't be
+    /// allocated. `this` is left unshared in that case.
+    pub fn try_clone(this: &Self) -> Result<Self, AllocError> {
+        let counter = this.try_counter()?;
+        increment(&counter.strong);
+
+        Ok(Self {
+            data: this.data,
+            share_count: this.share_count.clone(),
+        })
+    }
 }
 
+impl<T> LazyRc<T> {
+    /// Allocates an uninitialized value, to be written in place before [`LazyRc::assume_init`].
+    pub fn new_uninit() -> LazyRc<MaybeUninit<T>> {
+        LazyRc::new(Box::new_uninit())
+    }
+
+    /// Allocates an uninitialized value filled with zero bytes.
+    pub fn new_zeroed() -> LazyRc<MaybeUninit<T>> {
+        LazyRc::new(Box::new_zeroed())
+    }
+
+    /// Constructs a value that holds a [`LazyWeak`] pointer to itself.
+    ///
+    /// The weak pointer passed to `data_fn` doesn't upgrade until this returns. The counter block
+    /// is allocated up front, so the result always has one.
+    pub fn new_cyclic(data_fn: impl FnOnce(&LazyWeak<T>) -> T) -> Self {
+        let uninit = Self::new_uninit();
+        let weak = LazyWeak {
+            data: uninit.data.cast(),
+            counter: unsafe { NonNull::new_unchecked(Counter::alloc(0, 1) as *mut Counter) },
+        };
+        // If this panics, `weak` releases the counter block and `uninit` frees the data.
+        let value = data_fn(&weak);
+
+        let data = ManuallyDrop::new(uninit).data.cast::<T>();
+        let counter = ManuallyDrop::new(weak).counter;
+        unsafe {
+            data.as_ptr().write(value);
+            counter.as_ref().strong.set(1);
+            LazyRc {
+                data,
+                share_count: Cell::new(counter.as_ptr()),
+            }
+        }
+    }
+
+    /// Boxes `value` and pins it.
+    ///
+    /// The value is never moved by `LazyRc`: cloning only allocates a separate counter block, and
+    /// the box is freed in place once the last clone is dropped. Clones of a `Pin<LazyRc<T>>` are
+    /// themselves pinned, so the value can't be moved again.
+    pub fn pin(value: T) -> Pin<Self> {
+        unsafe { Pin::new_unchecked(Self::new(Box::new(value))) }
+    }
+
+    /// Returns the inner value if this is the only strong reference, otherwise returns the
+    /// `LazyRc` unchanged.
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        Self::try_into_box(this).map(|b| *b)
+    }
+
+    /// Returns the inner value if this is the only strong reference, otherwise drops this
+    /// reference and returns `None`.
+    #[inline]
+    pub fn into_inner(this: Self) -> Option<T> {
+        Self::try_unwrap(this).ok()
+    }
+
+    /// Returns the inner value if this is the only strong reference, otherwise clones it.
+    pub fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Self::try_unwrap(this).unwrap_or_else(|rc| (*rc).clone())
+    }
+}
+
+impl<T> LazyRc<[T]> {
+    /// Allocates an uninitialized slice, to be written in place before [`LazyRc::assume_init`].
+    pub fn new_uninit_slice(len: usize) -> LazyRc<[MaybeUninit<T>]> {
+        LazyRc::new(Box::new_uninit_slice(len))
+    }
+
+    /// Allocates an uninitialized slice filled with zero bytes.
+    pub fn new_zeroed_slice(len: usize) -> LazyRc<[MaybeUninit<T>]> {
+        LazyRc::new(Box::new_zeroed_slice(len))
+    }
+}
+
+impl<T> LazyRc<MaybeUninit<T>> {
+    /// Converts to `LazyRc<T>` without reallocating, keeping the counter block (if any).
+    ///
+    /// # Safety
+    ///
+    /// The value must have been initialized, e.g. through [`LazyRc::get_mut`].
+    pub unsafe fn assume_init(self) -> LazyRc<T> {
+        LazyRc::unsize(self, |ptr| ptr as *mut T)
+    }
+}
+
+impl<T> LazyRc<[MaybeUninit<T>]> {
+    /// Converts to `LazyRc<[T]>` without reallocating, keeping the counter block (if any).
+    ///
+    /// # Safety
+    ///
+    /// Every element must have been initialized, e.g. through [`LazyRc::get_mut`].
+    pub unsafe fn assume_init(self) -> LazyRc<[T]> {
+        LazyRc::unsize(self, |ptr| ptr as *mut [T])
+    }
+}
+
+impl LazyRc<dyn Any> {
+    /// Attempts to downcast to a concrete type, keeping the counter block shared.
+    pub fn downcast<T: Any>(self) -> Result<LazyRc<T>, Self> {
+        if (*self).is::<T>() {
+            unsafe { Ok(LazyRc::unsize(self, |ptr| ptr as *mut T)) }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Converts a `LazyRc<T>` into a `LazyRc<U>` where `T` unsizes to `U`, e.g.
+/// `unsize_lazyrc!(rc, dyn Trait)` or `unsize_lazyrc!(rc, [u8])`.
+///
+/// This is the stable equivalent of the `CoerceUnsized` implementation enabled by the `unstable`
+/// feature. Clones and weak pointers stay associated with the result.
+///
+/// Casts that aren't unsizing coercions are rejected:
+///
+/// ```compile_fail
+/// use lazyrc::{unsize_lazyrc, LazyRc};
+///
+/// let rc = LazyRc::new(Box::new(1u8));
+/// let _ = unsize_lazyrc!(rc, u32);
+/// ```
+#[macro_export]
+macro_rules! unsize_lazyrc {
+    ($rc:expr, $target:ty) => {{
+        let rc = $rc;
+        let cast = $crate::__unsize_cast(&rc, |ptr| -> *mut $target { ptr });
+        // SAFETY: The explicit return type only admits an unsizing coercion of the same pointer.
+        unsafe { $crate::LazyRc::unsize(rc, cast) }
+    }};
+}
+
+/// Pins the closure's argument type for [`unsize_lazyrc!`] so that its body is checked as a
+/// coercion from `*mut T`.
+#[doc(hidden)]
+#[inline(always)]
+pub fn __unsize_cast<T: ?Sized, U: ?Sized, F>(_: &LazyRc<T>, cast: F) -> F
+where
+    F: FnOnce(*mut T) -> *mut U,
+{
+    cast
+}
+
+// `DispatchFromDyn` isn't possible: it requires a single pointer field, and `LazyRc` also carries
+// its counter pointer.
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<LazyRc<U>> for LazyRc<T> {}
+
 impl<T: ?Sized> From<Box<T>> for LazyRc<T> {
     fn from(value: Box<T>) -> Self {
         Self::new(value)
     }
 }
 
+impl<T: ?Sized> From<Pin<Box<T>>> for Pin<LazyRc<T>> {
+    fn from(value: Pin<Box<T>>) -> Self {
+        // The boxed value stays where it is, see `LazyRc::pin`.
+        unsafe { Pin::new_unchecked(LazyRc::new(Pin::into_inner_unchecked(value))) }
+    }
+}
+
 impl<T> From<Vec<T>> for LazyRc<[T]> {
     fn from(value: Vec<T>) -> Self {
         Self::new(value.into_boxed_slice())
@@ -79,48 +687,210 @@ impl From<String> for LazyRc<str> {
     }
 }
 
+impl<T> From<T> for LazyRc<T> {
+    fn from(value: T) -> Self {
+        Self::new(Box::new(value))
+    }
+}
+
+impl<T: Clone> From<&[T]> for LazyRc<[T]> {
+    fn from(value: &[T]) -> Self {
+        Self::new(value.into())
+    }
+}
+
+impl From<&str> for LazyRc<str> {
+    fn from(value: &str) -> Self {
+        Self::new(value.into())
+    }
+}
+
+impl From<CString> for LazyRc<CStr> {
+    fn from(value: CString) -> Self {
+        Self::new(value.into_boxed_c_str())
+    }
+}
+
+impl From<&CStr> for LazyRc<CStr> {
+    fn from(value: &CStr) -> Self {
+        Self::new(value.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<OsString> for LazyRc<OsStr> {
+    fn from(value: OsString) -> Self {
+        Self::new(value.into_boxed_os_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&OsStr> for LazyRc<OsStr> {
+    fn from(value: &OsStr) -> Self {
+        Self::new(value.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PathBuf> for LazyRc<Path> {
+    fn from(value: PathBuf) -> Self {
+        Self::new(value.into_boxed_path())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&Path> for LazyRc<Path> {
+    fn from(value: &Path) -> Self {
+        Self::new(value.into())
+    }
+}
+
+impl<'a, B> From<Cow<'a, B>> for LazyRc<B>
+where
+    B: ToOwned + ?Sized,
+    LazyRc<B>: From<&'a B> + From<B::Owned>,
+{
+    fn from(value: Cow<'a, B>) -> Self {
+        match value {
+            Cow::Borrowed(b) => Self::from(b),
+            Cow::Owned(o) => Self::from(o),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for LazyRc<[T]> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> TryFrom<LazyRc<[T]>> for Vec<T> {
+    type Error = LazyRc<[T]>;
+
+    /// Succeeds without copying if the slice isn't shared.
+    fn try_from(value: LazyRc<[T]>) -> Result<Self, Self::Error> {
+        LazyRc::try_into_box(value).map(Vec::from)
+    }
+}
+
+impl TryFrom<LazyRc<str>> for String {
+    type Error = LazyRc<str>;
+
+    /// Succeeds without copying if the string isn't shared.
+    fn try_from(value: LazyRc<str>) -> Result<Self, Self::Error> {
+        LazyRc::try_into_box(value).map(String::from)
+    }
+}
+
+impl<T: Clone> From<LazyRc<T>> for Rc<T> {
+    /// Moves the value into a new `Rc` allocation, cloning it only if the `LazyRc` is shared.
+    fn from(value: LazyRc<T>) -> Self {
+        Rc::new(LazyRc::unwrap_or_clone(value))
+    }
+}
+
+impl<T: Clone> From<LazyRc<[T]>> for Rc<[T]> {
+    /// Moves the elements into a new `Rc` allocation, cloning them only if the `LazyRc` is shared.
+    fn from(value: LazyRc<[T]>) -> Self {
+        match LazyRc::try_into_box(value) {
+            Ok(boxed) => Rc::from(boxed),
+            Err(shared) => Rc::from(&*shared),
+        }
+    }
+}
+
+impl From<LazyRc<str>> for Rc<str> {
+    /// Copies the string into a new `Rc` allocation.
+    fn from(value: LazyRc<str>) -> Self {
+        Rc::from(&*value)
+    }
+}
+
+impl<T: Clone> From<Rc<T>> for LazyRc<T> {
+    /// Moves the value out of the `Rc` if it is the only strong reference, otherwise clones it.
+    fn from(value: Rc<T>) -> Self {
+        Self::new(Box::new(Rc::unwrap_or_clone(value)))
+    }
+}
+
+impl<T: Clone> From<LazyRc<T>> for Arc<T> {
+    /// Moves the value into a new `Arc` allocation, cloning it only if the `LazyRc` is shared.
+    fn from(value: LazyRc<T>) -> Self {
+        Arc::new(LazyRc::unwrap_or_clone(value))
+    }
+}
+
+impl<T: Clone> From<LazyRc<[T]>> for Arc<[T]> {
+    /// Moves the elements into a new `Arc` allocation, cloning them only if the `LazyRc` is shared.
+    fn from(value: LazyRc<[T]>) -> Self {
+        match LazyRc::try_into_box(value) {
+            Ok(boxed) => Arc::from(boxed),
+            Err(shared) => Arc::from(&*shared),
+        }
+    }
+}
+
+impl From<LazyRc<str>> for Arc<str> {
+    /// Copies the string into a new `Arc` allocation.
+    fn from(value: LazyRc<str>) -> Self {
+        Arc::from(&*value)
+    }
+}
+
+impl<T: Clone> From<Arc<T>> for LazyRc<T> {
+    /// Moves the value out of the `Arc` if it is the only strong reference, otherwise clones it.
+    fn from(value: Arc<T>) -> Self {
+        Self::new(Box::new(Arc::unwrap_or_clone(value)))
+    }
+}
+
 impl<T: ?Sized> Clone for LazyRc<T> {
+    /// Makes another handle to the same value.
+    ///
+    /// The first clone allocates the counter block. `self` only records it once the allocation
+    /// has succeeded, so it is never left half-shared; use [`LazyRc::try_clone`] to handle
+    /// allocation failure instead of aborting.
     fn clone(&self) -> Self {
-        unsafe {
-            if let Some(counter) = self.share_count.get().as_ref() {
-                counter.set(counter.get() + 1);
-            } else {
-                self.share_count
-                    .set(Box::into_raw(Box::new(Cell::new(2))) as *const _);
-            }
+        let counter = self.counter();
+        increment(&counter.strong);
 
-            Self {
-                data: self.data,
-                share_count: self.share_count.clone(),
-            }
+        Self {
+            data: self.data,
+            share_count: self.share_count.clone(),
         }
     }
 }
 
 impl<T: ?Sized> Drop for LazyRc<T> {
+    /// Drops the value if this is the last strong handle.
+    ///
+    /// If the value's destructor panics, the panic propagates after the counter block has been
+    /// released (and freed, unless [`LazyWeak`]s remain). The value's allocation is still freed
+    /// while unwinding, as it would be for a `Box`.
     fn drop(&mut self) {
         unsafe {
             let counter = self.share_count.get();
             if !counter.is_null() {
-                {
-                    let counter_ref = &*counter;
-                    let count = counter_ref.get();
-                    if count > 1 {
-                        counter_ref.set(count - 1);
-                        // Nothing to deallocate.
-                        return;
-                    }
+                let counter_ref = &*counter;
+                let strong = counter_ref.strong.get() - 1;
+                counter_ref.strong.set(strong);
+                if strong > 0 {
+                    // Nothing to deallocate.
+                    return;
                 }
-                // And drop the counter.
-                Box::from_raw(counter as *mut Cell<usize>);
+                // Release the weak reference held collectively by the strong owners before
+                // dropping the value, so the counter block isn't leaked if that panics. Any
+                // remaining weak pointers already fail to upgrade.
+                weak::release(counter);
             }
-            Box::from_raw(self.data.as_ptr());
+            drop(Box::from_raw(self.data.as_ptr()));
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::prelude::rust_2021::*;
     use std::sync::atomic::{AtomicU32, Ordering};
 
     use super::*;
@@ -169,4 +939,321 @@ mod test {
         drop(thing2);
         assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 2);
     }
+
+    #[test]
+    fn test_try_into_box() {
+        let buf: LazyRc<[u8]> = LazyRc::from(vec![1, 2, 3]);
+        let ptr = buf.as_ptr();
+        let peer = buf.clone();
+        let weak = LazyRc::downgrade(&buf);
+        let buf = LazyRc::try_into_box(buf).unwrap_err();
+        drop(peer);
+
+        let vec = LazyRc::try_into_box(buf).unwrap().into_vec();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert!(weak.upgrade().is_none());
+
+        let shared = LazyRc::new(Box::new(String::from("x")));
+        let peer = shared.clone();
+        assert_eq!(LazyRc::unwrap_or_clone(shared), "x");
+        assert_eq!(LazyRc::into_inner(peer).as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_make_mut() {
+        let mut buf: LazyRc<[u8]> = LazyRc::from(vec![1, 2, 3]);
+        LazyRc::get_mut(&mut buf).unwrap()[0] = 4;
+
+        let peer = buf.clone();
+        assert!(LazyRc::get_mut(&mut buf).is_none());
+        LazyRc::make_mut(&mut buf)[1] = 5;
+        assert_eq!(*buf, [4, 5, 3]);
+        assert_eq!(*peer, [4, 2, 3]);
+        assert_eq!(LazyRc::strong_count(&peer), 1);
+
+        let mut text: LazyRc<str> = LazyRc::from(String::from("abc"));
+        let weak = LazyRc::downgrade(&text);
+        let ptr = text.as_ptr();
+        LazyRc::make_mut(&mut text).make_ascii_uppercase();
+        assert_eq!(text.as_ptr(), ptr);
+        assert_eq!(&*text, "ABC");
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_introspection() {
+        let a: LazyRc<[u8]> = LazyRc::from(vec![1, 2]);
+        assert!(!LazyRc::has_counter(&a));
+        assert!(!LazyRc::is_shared(&a));
+        assert_eq!(LazyRc::strong_count(&a), 1);
+
+        let b = a.clone();
+        assert!(LazyRc::has_counter(&a));
+        assert!(LazyRc::is_shared(&b));
+        assert!(LazyRc::ptr_eq(&a, &b));
+        assert_eq!(LazyRc::as_ptr(&a), LazyRc::as_ptr(&b));
+        assert!(!LazyRc::ptr_eq(&a, &LazyRc::from(vec![1, 2])));
+
+        drop(b);
+        assert!(LazyRc::has_counter(&a));
+        assert!(!LazyRc::is_shared(&a));
+    }
+
+    #[test]
+    fn test_raw() {
+        let rc: LazyRc<str> = LazyRc::from(String::from("ffi"));
+        let (ptr, mut counter) = LazyRc::into_raw(rc);
+        assert!(counter.is_null());
+
+        unsafe {
+            LazyRc::increment_strong_count(ptr, &mut counter);
+            assert!(!counter.is_null());
+            let rc = LazyRc::from_raw(ptr, counter);
+            assert_eq!(LazyRc::strong_count(&rc), 2);
+            assert_eq!(&*rc, "ffi");
+
+            LazyRc::decrement_strong_count(ptr, counter);
+            assert_eq!(LazyRc::strong_count(&rc), 1);
+        }
+    }
+
+    #[test]
+    // The share count cell doesn't participate in `Hash`/`Ord`.
+    #[allow(clippy::mutable_key_type)]
+    fn test_traits() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let key: LazyRc<str> = LazyRc::from(String::from("b"));
+        let mut map = HashMap::new();
+        map.insert(key.clone(), 1);
+        assert_eq!(map.get("b"), Some(&1));
+
+        let set: BTreeSet<_> = [key, LazyRc::from(String::from("a"))].into();
+        assert_eq!(set.first().map(AsRef::as_ref), Some("a"));
+
+        let bytes: LazyRc<[u8]> = LazyRc::from(vec![1, 2]);
+        fn len(b: impl AsRef<[u8]>) -> usize {
+            b.as_ref().len()
+        }
+        assert_eq!(
+            format!("{:p}", bytes),
+            format!("{:p}", LazyRc::as_ptr(&bytes))
+        );
+        assert_eq!(len(bytes), 2);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let boxed: LazyRc<u32> = 5.into();
+        assert_eq!(*boxed, 5);
+
+        let borrowed: LazyRc<str> = Cow::Borrowed("cow").into();
+        let owned: LazyRc<[u8]> = Cow::<[u8]>::Owned(vec![1, 2]).into();
+        assert_eq!(&*borrowed, "cow");
+        assert_eq!(*owned, [1, 2]);
+
+        let c: LazyRc<CStr> = CString::new("c").unwrap().into();
+        assert_eq!(c.to_bytes(), b"c");
+
+        let collected: LazyRc<[u32]> = (0..3).collect();
+        let ptr = collected.as_ptr();
+        let peer = collected.clone();
+        let collected = Vec::try_from(collected).unwrap_err();
+        drop(peer);
+        let vec = Vec::try_from(collected).unwrap();
+        assert_eq!(vec.as_ptr(), ptr);
+
+        let text = String::try_from(LazyRc::from("text")).unwrap();
+        assert_eq!(text, "text");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_std_conversions() {
+        use std::ffi::OsStr;
+        use std::path::{Path, PathBuf};
+
+        let path: LazyRc<Path> = PathBuf::from("/tmp").into();
+        assert_eq!(&*path, Path::new("/tmp"));
+        let os: LazyRc<OsStr> = OsStr::new("os").into();
+        assert_eq!(&*os, "os");
+    }
+
+    #[test]
+    fn test_rc_interop() {
+        let lazy = LazyRc::new(Box::new(vec![1u8]));
+        let peer = lazy.clone();
+        let rc: Rc<Vec<u8>> = Rc::from(lazy);
+        // The shared value was cloned, leaving `peer` alone.
+        assert_eq!(*rc, *peer);
+        assert_eq!(LazyRc::strong_count(&peer), 1);
+        let arc: Arc<Vec<u8>> = Arc::from(peer);
+        assert_eq!(*arc, [1]);
+
+        let slice: Rc<[String]> = Rc::from(LazyRc::from(vec![String::from("a")]));
+        assert_eq!(*slice, ["a"]);
+        let text: Arc<str> = Arc::from(LazyRc::from("text"));
+        assert_eq!(&*text, "text");
+
+        let rc = Rc::new(String::from("moved"));
+        let addr = rc.as_ptr();
+        let lazy: LazyRc<String> = LazyRc::from(rc);
+        // Unwrapping moved the `String`, so its buffer wasn't copied.
+        assert_eq!(lazy.as_ptr(), addr);
+
+        let arc = Arc::new(3);
+        let peer = arc.clone();
+        let lazy: LazyRc<i32> = LazyRc::from(arc);
+        assert_eq!(*lazy, *peer);
+        assert_eq!(Arc::strong_count(&peer), 1);
+    }
+
+    #[test]
+    fn test_pin() {
+        use core::marker::PhantomPinned;
+
+        let pinned = LazyRc::pin((5, PhantomPinned));
+        let addr: *const _ = &*pinned;
+        let peer = pinned.clone();
+        assert_eq!(&*peer as *const _, addr);
+        drop(pinned);
+        assert_eq!(&*peer as *const _, addr);
+        assert_eq!(peer.0, 5);
+
+        let boxed: Pin<LazyRc<[u8]>> = Box::into_pin(Box::<[u8]>::from(&[1][..])).into();
+        assert_eq!(*boxed, [1]);
+
+        let unique = LazyRc::new(Box::new(1));
+        let shared = LazyRc::into_pin(unique).unwrap().clone();
+        let weak = LazyRc::new(Box::new(2));
+        let _weak = LazyRc::downgrade(&weak);
+        assert!(LazyRc::into_pin(weak).is_err());
+        assert_eq!(*shared, 1);
+    }
+
+    #[test]
+    fn test_unsize() {
+        let concrete = LazyRc::new(Box::new(String::from("any")));
+        let peer = concrete.clone();
+        let weak = LazyRc::downgrade(&concrete);
+
+        let any = unsize_lazyrc!(concrete, dyn Any);
+        assert_eq!(LazyRc::strong_count(&any), 2);
+        let any = any.downcast::<u32>().unwrap_err();
+        let back = any.downcast::<String>().unwrap();
+        assert!(LazyRc::ptr_eq(&back, &peer));
+
+        drop(back);
+        drop(peer);
+        assert!(weak.upgrade().is_none());
+
+        let display: LazyRc<dyn Display> = unsize_lazyrc!(LazyRc::new(Box::new(1)), dyn Display);
+        assert_eq!(display.to_string(), "1");
+        let slice = unsize_lazyrc!(LazyRc::new(Box::new([1u8, 2])), [u8]);
+        assert_eq!(slice.len(), 2);
+    }
+
+    #[test]
+    fn test_uninit() {
+        let mut buf = LazyRc::<[u32]>::new_uninit_slice(3);
+        let ptr = LazyRc::as_ptr(&buf) as *const u32;
+        for (i, slot) in LazyRc::get_mut(&mut buf).unwrap().iter_mut().enumerate() {
+            slot.write(i as u32);
+        }
+        let buf = unsafe { buf.assume_init() };
+        assert_eq!(*buf, [0, 1, 2]);
+        assert_eq!(buf.as_ptr(), ptr);
+
+        let zeroed = unsafe { LazyRc::<[u64]>::new_zeroed_slice(2).assume_init() };
+        assert_eq!(*zeroed, [0, 0]);
+
+        let mut one = LazyRc::<String>::new_uninit();
+        LazyRc::get_mut(&mut one).unwrap().write("init".into());
+        assert_eq!(*unsafe { one.assume_init() }, "init");
+    }
+
+    #[test]
+    fn test_new_cyclic() {
+        struct Node {
+            me: LazyWeak<Node>,
+            value: u32,
+        }
+
+        let node = LazyRc::new_cyclic(|me| {
+            assert!(me.upgrade().is_none());
+            Node {
+                me: me.clone(),
+                value: 3,
+            }
+        });
+        let again = node.me.upgrade().unwrap();
+        assert!(LazyRc::ptr_eq(&node, &again));
+        assert_eq!(again.value, 3);
+        assert_eq!(LazyRc::weak_count(&node), 1);
+        drop(again);
+        drop(node);
+    }
+
+    #[test]
+    fn test_try_clone() {
+        let rc = LazyRc::new(Box::new(1));
+        FAIL_NEXT_ALLOC.with(|fail| fail.set(true));
+        assert_eq!(LazyRc::try_clone(&rc).unwrap_err(), AllocError);
+        assert!(!LazyRc::has_counter(&rc));
+
+        let peer = LazyRc::try_clone(&rc).unwrap();
+        assert_eq!(LazyRc::strong_count(&peer), 2);
+        FAIL_NEXT_ALLOC.with(|fail| fail.set(true));
+        // Once the counter exists, cloning doesn't allocate.
+        let third = LazyRc::try_clone(&rc).unwrap();
+        FAIL_NEXT_ALLOC.with(|fail| fail.set(false));
+        assert_eq!(LazyRc::strong_count(&third), 3);
+    }
+
+    /// Runs only when spawned by `test_overflow_aborts`.
+    #[test]
+    fn overflow_child() {
+        if std::env::var_os("LAZYRC_OVERFLOW_CHILD").is_none() {
+            return;
+        }
+        let rc = LazyRc::new(Box::new(()));
+        // Forge a count that's about to wrap.
+        rc.counter().strong.set(usize::MAX);
+        let _clone = rc.clone();
+        unreachable!("clone should have aborted");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "spawns a process")]
+    fn test_overflow_aborts() {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "test::overflow_child", "--nocapture"])
+            .env("LAZYRC_OVERFLOW_CHILD", "1")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("should have aborted"), "{stdout}");
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            assert_eq!(output.status.signal(), Some(6), "expected SIGABRT");
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_coerce_unsized() {
+        // Coerce existing bindings, so it's the `LazyRc` (not the `Box`) that unsizes.
+        let array = LazyRc::new(Box::new([1u8, 2]));
+        let peer = array.clone();
+        let slice: LazyRc<[u8]> = array;
+        assert_eq!(*slice, [1, 2]);
+        assert_eq!(LazyRc::strong_count(&slice), 2);
+
+        let number = LazyRc::new(Box::new(5i32));
+        let display: LazyRc<dyn Display> = number;
+        assert_eq!(display.to_string(), "5");
+        drop(peer);
+    }
 }
@@ -0,0 +1,138 @@
+use core::fmt::{self, Debug, Display};
+use core::ops::{Deref, RangeBounds};
+use core::ptr::NonNull;
+
+use crate::LazyRc;
+
+/// A [`LazyRc`] projected onto part of its value, e.g. a field or a sub-slice.
+///
+/// Created with [`LazyRc::map`] or [`LazyRc::slice`]. It keeps the original `LazyRc<T>` (and so
+/// the original allocation and counter block) alive, and clones share that same counter.
+pub struct MappedLazyRc<T: ?Sized, U: ?Sized> {
+    owner: LazyRc<T>,
+    data: NonNull<U>,
+}
+
+impl<T: ?Sized> LazyRc<T> {
+    /// Projects this `LazyRc` onto a part of its value without copying.
+    pub fn map<U: ?Sized>(this: Self, f: impl FnOnce(&T) -> &U) -> MappedLazyRc<T, U> {
+        let data = NonNull::from(f(&this));
+        MappedLazyRc { owner: this, data }
+    }
+}
+
+impl<T> LazyRc<[T]> {
+    /// Returns a handle to a sub-slice, sharing this `LazyRc`'s allocation and counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(this: &Self, range: impl RangeBounds<usize>) -> MappedLazyRc<[T], [T]> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        Self::map(this.clone(), |s| &s[range])
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> MappedLazyRc<T, U> {
+    /// Projects further onto a part of the mapped value.
+    pub fn map<V: ?Sized>(this: Self, f: impl FnOnce(&U) -> &V) -> MappedLazyRc<T, V> {
+        let data = NonNull::from(f(&this));
+        MappedLazyRc {
+            owner: this.owner,
+            data,
+        }
+    }
+
+    /// Returns the `LazyRc` that owns the whole value.
+    #[inline]
+    pub fn owner(this: &Self) -> &LazyRc<T> {
+        &this.owner
+    }
+
+    /// Returns the `LazyRc` that owns the whole value, dropping the projection.
+    #[inline]
+    pub fn into_owner(this: Self) -> LazyRc<T> {
+        this.owner
+    }
+}
+
+impl<T: ?Sized, U> MappedLazyRc<T, [U]> {
+    /// Returns a handle to a sub-slice of the mapped slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(this: &Self, range: impl RangeBounds<usize>) -> Self {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        Self::map(this.clone(), |s| &s[range])
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedLazyRc<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // The owner keeps the value alive, and `LazyRc` never moves it.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> AsRef<U> for MappedLazyRc<T, U> {
+    #[inline]
+    fn as_ref(&self) -> &U {
+        self
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Clone for MappedLazyRc<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            owner: self.owner.clone(),
+            data: self.data,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + Debug> Debug for MappedLazyRc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + Display> Display for MappedLazyRc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_slice() {
+        let packet: LazyRc<[u8]> = LazyRc::from(vec![0xff, 1, 2, 3, 0xee]);
+        let payload = LazyRc::slice(&packet, 1..4);
+        assert_eq!(*payload, [1, 2, 3]);
+        assert_eq!(LazyRc::strong_count(&packet), 2);
+
+        let tail = MappedLazyRc::slice(&payload, 1..);
+        assert_eq!(*tail, [2, 3]);
+        assert!(core::ptr::eq(&tail[0], &packet[2]));
+
+        drop(packet);
+        drop(payload);
+        let owner = MappedLazyRc::into_owner(tail);
+        assert_eq!(LazyRc::strong_count(&owner), 1);
+        assert_eq!(owner.len(), 5);
+
+        let pair = LazyRc::new(Box::new((String::from("a"), 1)));
+        let first = LazyRc::map(pair, |p| p.0.as_str());
+        assert_eq!(&*first.clone(), "a");
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::boxed::Box;
+use core::cell::{Cell, OnceCell};
+use core::fmt::{self, Debug, Display};
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+
+use crate::increment;
+
+/// A [`LazyRc`](crate::LazyRc) whose lazily allocated counter block also caches metadata of type
+/// `M`, such as a hash or a parsed header of the value.
+///
+/// Like the share count, the metadata slot is only allocated once it's needed: on the first
+/// clone, or the first call to [`MetaLazyRc::meta_or_init`]. The metadata is computed at most once
+/// and shared by all clones. It is dropped with the last clone, or by [`MetaLazyRc::try_into_box`].
+pub struct MetaLazyRc<T: ?Sized, M> {
+    data: NonNull<T>,
+    block: Cell<*const MetaBlock<M>>,
+}
+
+/// The block shared by all clones of a [`MetaLazyRc`].
+struct MetaBlock<M> {
+    count: Cell<usize>,
+    meta: OnceCell<M>,
+}
+
+impl<T: ?Sized, M> MetaLazyRc<T, M> {
+    #[inline]
+    pub fn new(inner: Box<T>) -> Self {
+        unsafe {
+            MetaLazyRc {
+                // Box always returns a non-null pointer.
+                data: NonNull::new_unchecked(Box::into_raw(inner)),
+                block: Cell::new(ptr::null()),
+            }
+        }
+    }
+
+    /// Returns the metadata, if it has been initialized.
+    #[inline]
+    pub fn meta(this: &Self) -> Option<&M> {
+        this.block_ref().and_then(|block| block.meta.get())
+    }
+
+    /// Returns the metadata, computing it from the value with `f` if it hasn't been yet.
+    ///
+    /// This allocates the counter block if the value hasn't been shared yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` reentrantly initializes the metadata of the same value. If `f` panics, the
+    /// panic propagates and the metadata stays uninitialized.
+    pub fn meta_or_init(this: &Self, f: impl FnOnce(&T) -> M) -> &M {
+        this.block().meta.get_or_init(|| f(this))
+    }
+
+    /// Gets the number of `MetaLazyRc` pointers to this value.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.block_ref().map_or(1, |block| block.count.get())
+    }
+
+    /// Returns the inner box if this is the only reference, freeing the counter block and
+    /// dropping the metadata (if any). Otherwise, the `MetaLazyRc` is returned unchanged.
+    pub fn try_into_box(this: Self) -> Result<Box<T>, Self> {
+        if Self::strong_count(&this) != 1 {
+            return Err(this);
+        }
+        let this = ManuallyDrop::new(this);
+        unsafe {
+            let value = Box::from_raw(this.data.as_ptr());
+            let block = this.block.get();
+            if !block.is_null() {
+                drop(Box::from_raw(block as *mut MetaBlock<M>));
+            }
+            Ok(value)
+        }
+    }
+
+    /// Returns true if the two `MetaLazyRc`s point to the same value.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::eq(this.data.as_ptr(), other.data.as_ptr())
+    }
+
+    #[inline]
+    fn block_ref(&self) -> Option<&MetaBlock<M>> {
+        unsafe { self.block.get().as_ref() }
+    }
+
+    /// Returns the counter block, allocating it if this value hasn't been shared yet.
+    fn block(&self) -> &MetaBlock<M> {
+        unsafe {
+            if let Some(block) = self.block.get().as_ref() {
+                return block;
+            }
+            let block = Box::into_raw(Box::new(MetaBlock {
+                count: Cell::new(1),
+                meta: OnceCell::new(),
+            }));
+            self.block.set(block);
+            &*block
+        }
+    }
+}
+
+impl<T: ?Sized + Debug, M> Debug for MetaLazyRc<T, M> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display, M> Display for MetaLazyRc<T, M> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, M> Deref for MetaLazyRc<T, M> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized, M> AsRef<T> for MetaLazyRc<T, M> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized, M> From<Box<T>> for MetaLazyRc<T, M> {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ?Sized, M> Clone for MetaLazyRc<T, M> {
+    fn clone(&self) -> Self {
+        increment(&self.block().count);
+
+        Self {
+            data: self.data,
+            block: self.block.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, M> Drop for MetaLazyRc<T, M> {
+    fn drop(&mut self) {
+        unsafe {
+            let block = self.block.get();
+            if let Some(block) = block.as_ref() {
+                let count = block.count.get() - 1;
+                block.count.set(count);
+                if count > 0 {
+                    // Nothing to deallocate.
+                    return;
+                }
+            }
+            // Take ownership of the value first, so it's still freed if dropping the metadata
+            // panics (and vice versa).
+            let value = Box::from_raw(self.data.as_ptr());
+            if !block.is_null() {
+                drop(Box::from_raw(block as *mut MetaBlock<M>));
+            }
+            drop(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::hash::BuildHasher;
+    use std::collections::hash_map::RandomState;
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_meta() {
+        let hasher = RandomState::new();
+        let hash = |value: &[u8]| hasher.hash_one(value);
+
+        let buf: MetaLazyRc<[u8], u64> = MetaLazyRc::new(Box::new([1, 2, 3]));
+        assert!(MetaLazyRc::meta(&buf).is_none());
+        // Initializing the metadata allocates the block, even before the first clone.
+        let expected = *MetaLazyRc::meta_or_init(&buf, hash);
+        assert_eq!(expected, hash(&buf));
+        assert_eq!(MetaLazyRc::strong_count(&buf), 1);
+
+        let peer = buf.clone();
+        assert_eq!(MetaLazyRc::strong_count(&peer), 2);
+        assert_eq!(
+            *MetaLazyRc::meta_or_init(&peer, |_| unreachable!()),
+            expected
+        );
+
+        let buf = MetaLazyRc::try_into_box(buf).unwrap_err();
+        drop(peer);
+        assert_eq!(*MetaLazyRc::try_into_box(buf).unwrap(), [1, 2, 3]);
+
+        let text: MetaLazyRc<str, String> = MetaLazyRc::new("header: value".into());
+        let peer = text.clone();
+        let name = MetaLazyRc::meta_or_init(&peer, |t| t.split(':').next().unwrap().into());
+        assert_eq!(MetaLazyRc::meta(&text).unwrap(), "header");
+        assert_eq!(name, "header");
+    }
+}
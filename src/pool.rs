@@ -0,0 +1,61 @@
+//! A thread-local free-list of counter blocks.
+//!
+//! The first clone of every `LazyRc` allocates a tiny counter block, and the last drop frees it.
+//! When many values are shared briefly, recycling those blocks turns the allocator round-trip into
+//! a push/pop on a per-thread list.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::Counter;
+
+/// The maximum number of free counter blocks cached per thread.
+const MAX_CACHED: usize = 1024;
+
+std::thread_local! {
+    // The blocks are handed out as-is, so they must stay individually boxed.
+    #[allow(clippy::vec_box)]
+    static FREE_LIST: RefCell<Vec<Box<Counter>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Takes a cached counter block, if any.
+#[inline]
+pub(crate) fn take() -> Option<Box<Counter>> {
+    FREE_LIST
+        .try_with(|list| list.borrow_mut().pop())
+        .ok()
+        .flatten()
+}
+
+/// Returns a counter block to the pool, freeing it if the pool is full or already torn down.
+#[inline]
+pub(crate) fn give(counter: Box<Counter>) {
+    let _ = FREE_LIST.try_with(|list| {
+        let mut list = list.borrow_mut();
+        if list.len() < MAX_CACHED {
+            list.push(counter);
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+
+    use crate::LazyRc;
+
+    #[test]
+    fn test_reuse() {
+        let a = LazyRc::new(Box::new(1));
+        let first = {
+            let _b = a.clone();
+            a.share_count.get()
+        };
+        drop(a);
+
+        let c = LazyRc::new(Box::new(2));
+        let _d = c.clone();
+        assert_eq!(c.share_count.get(), first);
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::boxed::Box;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::LazyRc;
+
+impl<T: ?Sized + Serialize> Serialize for LazyRc<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes into a `Box<T>` first, so the result is unshared and no counter is allocated.
+impl<'de, T: ?Sized> Deserialize<'de> for LazyRc<T>
+where
+    Box<T>: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Box::deserialize(deserializer).map(LazyRc::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+
+    use serde_test::{assert_tokens, Token};
+
+    use crate::LazyRc;
+
+    #[test]
+    fn test_round_trip() {
+        let text: LazyRc<str> = LazyRc::from("config");
+        assert_tokens(&text, &[Token::Str("config")]);
+
+        let bytes: LazyRc<[u8]> = LazyRc::from(vec![1, 2]);
+        assert_tokens(
+            &bytes,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::SeqEnd,
+            ],
+        );
+
+        let shared = LazyRc::new(Box::new(7u32));
+        let _peer = shared.clone();
+        assert_tokens(&shared, &[Token::U32(7)]);
+    }
+}
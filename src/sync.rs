@@ -0,0 +1,232 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+#[cfg(not(loom))]
+use core::sync::atomic::{self, AtomicPtr, AtomicUsize, Ordering};
+// Model-checked with `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#[cfg(loom)]
+use loom::sync::atomic::{self, AtomicPtr, AtomicUsize, Ordering};
+
+use crate::LazyRc;
+
+/// The largest share count before cloning aborts, leaving headroom for concurrent increments.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+/// A thread-safe lazy ref-cell that acts like a box until cloned.
+///
+/// This is the atomic counterpart to [`LazyRc`](crate::LazyRc): the share count is only allocated
+/// the first time the `LazyArc` is cloned, and is installed with a compare-and-swap so concurrent
+/// first clones agree on a single counter.
+pub struct LazyArc<T: ?Sized> {
+    data: NonNull<T>,
+    share_count: AtomicPtr<AtomicUsize>,
+}
+
+// Same bounds as `std::sync::Arc`.
+unsafe impl<T: ?Sized + Sync + Send> Send for LazyArc<T> {}
+unsafe impl<T: ?Sized + Sync + Send> Sync for LazyArc<T> {}
+
+impl<T: ?Sized> Default for LazyArc<T>
+where
+    Box<T>: Default,
+{
+    fn default() -> Self {
+        let boxed: Box<T> = Default::default();
+        Self::new(boxed)
+    }
+}
+
+impl<T: ?Sized> Debug for LazyArc<T>
+where
+    T: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Display for LazyArc<T>
+where
+    T: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for LazyArc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T: ?Sized> LazyArc<T> {
+    #[inline]
+    pub fn new(inner: Box<T>) -> Self {
+        unsafe {
+            LazyArc {
+                // Box always returns a non-null pointer.
+                data: NonNull::new_unchecked(Box::into_raw(inner)),
+                share_count: AtomicPtr::new(ptr::null_mut()),
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> LazyRc<T> {
+    /// Converts this into a [`LazyArc`] if this is the only strong reference, reusing the boxed
+    /// value without copying it. Otherwise, the `LazyRc` is returned unchanged.
+    ///
+    /// Like [`LazyRc::try_into_box`], outstanding [`LazyWeak`](crate::LazyWeak)s will no longer
+    /// upgrade.
+    #[inline]
+    pub fn try_into_sync(this: Self) -> Result<LazyArc<T>, Self> {
+        Self::try_into_box(this).map(LazyArc::new)
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for LazyArc<T> {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Vec<T>> for LazyArc<[T]> {
+    fn from(value: Vec<T>) -> Self {
+        Self::new(value.into_boxed_slice())
+    }
+}
+
+impl From<String> for LazyArc<str> {
+    fn from(value: String) -> Self {
+        Self::new(value.into_boxed_str())
+    }
+}
+
+impl<T: ?Sized> Clone for LazyArc<T> {
+    fn clone(&self) -> Self {
+        let mut counter = self.share_count.load(Ordering::Acquire);
+        if counter.is_null() {
+            // Speculatively allocate a counter accounting for both `self` and the clone.
+            let fresh = Box::into_raw(Box::new(AtomicUsize::new(2)));
+            match self.share_count.compare_exchange(
+                ptr::null_mut(),
+                fresh,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    return Self {
+                        data: self.data,
+                        share_count: AtomicPtr::new(fresh),
+                    }
+                }
+                Err(installed) => {
+                    // Another thread won the race, free our counter and use theirs.
+                    unsafe { drop(Box::from_raw(fresh)) };
+                    counter = installed;
+                }
+            }
+        }
+
+        // Like `Arc`, a relaxed increment is sufficient because we already hold a reference.
+        let old = unsafe { (*counter).fetch_add(1, Ordering::Relaxed) };
+        // And like `Arc`, abort well before the count can wrap, even if other threads race past.
+        if old > MAX_REFCOUNT {
+            crate::abort();
+        }
+        Self {
+            data: self.data,
+            share_count: AtomicPtr::new(counter),
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for LazyArc<T> {
+    fn drop(&mut self) {
+        unsafe {
+            // We have exclusive access, so any stores to the counter pointer are already visible.
+            let counter = self.share_count.load(Ordering::Relaxed);
+            if !counter.is_null() {
+                if (*counter).fetch_sub(1, Ordering::Release) != 1 {
+                    // Nothing to deallocate.
+                    return;
+                }
+                // Synchronize with the other owners' releases before tearing down.
+                atomic::fence(Ordering::Acquire);
+                // And drop the counter.
+                drop(Box::from_raw(counter));
+            }
+            drop(Box::from_raw(self.data.as_ptr()));
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod test {
+    use std::prelude::rust_2021::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+
+    use super::*;
+
+    static DROP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+    struct DropTest;
+
+    impl Drop for DropTest {
+        fn drop(&mut self) {
+            DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_threaded() {
+        let thing = LazyArc::new(Box::new((7u32, DropTest)));
+        // Race the first clone from several threads at once.
+        let sums: Vec<u32> = thread::scope(|s| {
+            let workers: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        let clones: Vec<_> = (0..16).map(|_| thing.clone()).collect();
+                        clones.iter().map(|c| c.0).sum()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        assert!(sums.iter().all(|&s| s == 7 * 16));
+
+        let moved = thing.clone();
+        thread::spawn(move || assert_eq!(moved.0, 7))
+            .join()
+            .unwrap();
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 0);
+        drop(thing);
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_try_into_sync() {
+        let buf: LazyRc<[u8]> = LazyRc::from(vec![1, 2, 3]);
+        let addr = buf.as_ptr();
+        let peer = buf.clone();
+        let buf = LazyRc::try_into_sync(buf).unwrap_err();
+        drop(peer);
+
+        let buf = LazyRc::try_into_sync(buf).unwrap();
+        assert_eq!(buf.as_ptr(), addr);
+        let worker = buf.clone();
+        thread::spawn(move || assert_eq!(*worker, [1, 2, 3]))
+            .join()
+            .unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+use alloc::boxed::Box;
+use core::cell::Cell;
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+use crate::increment;
+
+/// Set on the handle's pointer once it points at a [`Shared`] block rather than the value.
+const SHARED_TAG: usize = 1;
+
+/// A [`LazyRc`](crate::LazyRc) that is a single pointer wide.
+///
+/// While unshared, the handle points straight at the boxed value. The first clone allocates a
+/// block holding the share count and the value pointer, and redirects the handle (and all later
+/// clones) to it, marking the pointer with its low bit. Shared handles therefore dereference
+/// through one extra pointer.
+///
+/// The tag bit requires `T` to be aligned to at least 2 bytes, which rules out `u8`, `i8`, `bool`
+/// and byte arrays like `[u8; N]` (box those in a `LazyRc` instead, or wrap them in a more
+/// aligned type). Constructing a `ThinLazyRc` for such a type fails when the code is built, though
+/// not under `cargo check`:
+///
+/// ```compile_fail
+/// use lazyrc::ThinLazyRc;
+///
+/// let _ = ThinLazyRc::new(Box::new([0u8; 64]));
+/// ```
+pub struct ThinLazyRc<T> {
+    ptr: Cell<NonNull<u8>>,
+    // Owns a `T`, like `Box<T>`.
+    _marker: PhantomData<T>,
+}
+
+/// The block a shared [`ThinLazyRc`] points to.
+struct Shared<T> {
+    count: Cell<usize>,
+    data: NonNull<T>,
+}
+
+impl<T> ThinLazyRc<T> {
+    #[inline]
+    pub fn new(inner: Box<T>) -> Self {
+        const {
+            assert!(
+                mem::align_of::<T>() > SHARED_TAG,
+                "ThinLazyRc needs a low bit of the value pointer for its tag"
+            )
+        };
+        ThinLazyRc {
+            ptr: Cell::new(NonNull::from(Box::leak(inner)).cast()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the number of `ThinLazyRc` pointers to this value.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.shared().map_or(1, |shared| shared.count.get())
+    }
+
+    /// Returns the inner box if this is the only reference, freeing the share count (if any).
+    /// Otherwise, the `ThinLazyRc` is returned unchanged.
+    pub fn try_into_box(this: Self) -> Result<Box<T>, Self> {
+        let this = ManuallyDrop::new(this);
+        unsafe {
+            let data = match this.shared() {
+                Some(shared) if shared.count.get() != 1 => {
+                    return Err(ManuallyDrop::into_inner(this))
+                }
+                Some(shared) => {
+                    let data = shared.data;
+                    drop(Box::from_raw(this.shared_ptr()));
+                    data
+                }
+                None => this.ptr.get().cast(),
+            };
+            Ok(Box::from_raw(data.as_ptr()))
+        }
+    }
+
+    /// Returns true if the two `ThinLazyRc`s point to the same value.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.data() == other.data()
+    }
+
+    #[inline]
+    fn is_shared(&self) -> bool {
+        self.ptr.get().addr().get() & SHARED_TAG != 0
+    }
+
+    /// The shared block's pointer, with the tag stripped. Only meaningful if `is_shared`.
+    #[inline]
+    fn shared_ptr(&self) -> *mut Shared<T> {
+        self.ptr
+            .get()
+            .as_ptr()
+            .map_addr(|addr| addr & !SHARED_TAG)
+            .cast()
+    }
+
+    #[inline]
+    fn shared(&self) -> Option<&Shared<T>> {
+        if self.is_shared() {
+            Some(unsafe { &*self.shared_ptr() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn data(&self) -> NonNull<T> {
+        match self.shared() {
+            Some(shared) => shared.data,
+            None => self.ptr.get().cast(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for ThinLazyRc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Display> Display for ThinLazyRc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: Default> Default for ThinLazyRc<T> {
+    fn default() -> Self {
+        Self::new(Box::default())
+    }
+}
+
+impl<T> Deref for ThinLazyRc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data().as_ref() }
+    }
+}
+
+impl<T> AsRef<T> for ThinLazyRc<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> From<Box<T>> for ThinLazyRc<T> {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<T> for ThinLazyRc<T> {
+    fn from(value: T) -> Self {
+        Self::new(Box::new(value))
+    }
+}
+
+impl<T> Clone for ThinLazyRc<T> {
+    fn clone(&self) -> Self {
+        match self.shared() {
+            Some(shared) => increment(&shared.count),
+            None => {
+                let shared = Box::into_raw(Box::new(Shared {
+                    count: Cell::new(2),
+                    data: self.ptr.get().cast::<T>(),
+                }));
+                let tagged = shared.cast::<u8>().map_addr(|addr| addr | SHARED_TAG);
+                // Box always returns a non-null pointer, and tagging only sets a bit.
+                self.ptr.set(unsafe { NonNull::new_unchecked(tagged) });
+            }
+        }
+
+        Self {
+            ptr: self.ptr.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for ThinLazyRc<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let data = match self.shared() {
+                Some(shared) => {
+                    let count = shared.count.get() - 1;
+                    shared.count.set(count);
+                    if count > 0 {
+                        // Nothing to deallocate.
+                        return;
+                    }
+                    let data = shared.data;
+                    // Free the block first, so it isn't leaked if dropping the value panics.
+                    drop(Box::from_raw(self.shared_ptr()));
+                    data
+                }
+                None => self.ptr.get().cast(),
+            };
+            drop(Box::from_raw(data.as_ptr()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::mem::size_of;
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_thin() {
+        assert_eq!(size_of::<ThinLazyRc<u64>>(), size_of::<usize>());
+
+        let rc = ThinLazyRc::new(Box::new(String::from("thin")));
+        let addr = &*rc as *const String;
+        assert_eq!(ThinLazyRc::strong_count(&rc), 1);
+
+        let peer = rc.clone();
+        let third = peer.clone();
+        assert_eq!(ThinLazyRc::strong_count(&rc), 3);
+        assert!(ThinLazyRc::ptr_eq(&rc, &third));
+        // Sharing redirects the handle, but never moves the value.
+        assert_eq!(&*peer as *const String, addr);
+        assert_eq!(*third, "thin");
+
+        let rc = ThinLazyRc::try_into_box(rc).unwrap_err();
+        drop(peer);
+        drop(third);
+        let boxed = ThinLazyRc::try_into_box(rc).unwrap();
+        assert_eq!(&*boxed as *const String, addr);
+
+        let unit: ThinLazyRc<u16> = ThinLazyRc::from(7);
+        drop(unit.clone());
+        assert_eq!(*unit, 7);
+    }
+}
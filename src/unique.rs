@@ -0,0 +1,160 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display};
+use core::ops::{Deref, DerefMut};
+
+use crate::LazyRc;
+
+/// A uniquely owned value that can later be turned into a [`LazyRc`] without copying.
+///
+/// Until a `LazyRc` is shared, it is really just a box. `UniqueLazyRc` is that box with the
+/// properties a `LazyRc` can't have: it is `Send` and `Sync` when `T` is, and gives mutable
+/// access. Build the value (on any thread), then call [`UniqueLazyRc::share`] where sharing
+/// begins; [`LazyRc::try_into_unique`] goes back the other way.
+pub struct UniqueLazyRc<T: ?Sized>(Box<T>);
+
+impl<T: ?Sized> UniqueLazyRc<T> {
+    #[inline]
+    pub fn new(inner: Box<T>) -> Self {
+        UniqueLazyRc(inner)
+    }
+
+    /// Converts this into a [`LazyRc`], reusing the allocation. Doesn't allocate.
+    #[inline]
+    pub fn share(self) -> LazyRc<T> {
+        LazyRc::new(self.0)
+    }
+
+    /// Returns the inner box.
+    #[inline]
+    pub fn into_box(this: Self) -> Box<T> {
+        this.0
+    }
+}
+
+impl<T: ?Sized> LazyRc<T> {
+    /// Converts this into a [`UniqueLazyRc`] if this is the only strong reference, without copying
+    /// the value. Otherwise, the `LazyRc` is returned unchanged.
+    ///
+    /// Like [`LazyRc::try_into_box`], outstanding [`LazyWeak`](crate::LazyWeak)s will no longer
+    /// upgrade.
+    #[inline]
+    pub fn try_into_unique(this: Self) -> Result<UniqueLazyRc<T>, Self> {
+        Self::try_into_box(this).map(UniqueLazyRc)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for UniqueLazyRc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for UniqueLazyRc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Default for UniqueLazyRc<T>
+where
+    Box<T>: Default,
+{
+    fn default() -> Self {
+        Self::new(Box::default())
+    }
+}
+
+impl<T: ?Sized> Deref for UniqueLazyRc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> DerefMut for UniqueLazyRc<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for UniqueLazyRc<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized> AsMut<T> for UniqueLazyRc<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for UniqueLazyRc<T> {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<T> for UniqueLazyRc<T> {
+    fn from(value: T) -> Self {
+        Self::new(Box::new(value))
+    }
+}
+
+impl<T> From<Vec<T>> for UniqueLazyRc<[T]> {
+    fn from(value: Vec<T>) -> Self {
+        Self::new(value.into_boxed_slice())
+    }
+}
+
+impl From<String> for UniqueLazyRc<str> {
+    fn from(value: String) -> Self {
+        Self::new(value.into_boxed_str())
+    }
+}
+
+impl<T: ?Sized> From<UniqueLazyRc<T>> for LazyRc<T> {
+    fn from(value: UniqueLazyRc<T>) -> Self {
+        value.share()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_unique() {
+        let mut buf: UniqueLazyRc<[u8]> = UniqueLazyRc::from(vec![0; 4]);
+        let addr = buf.as_ptr();
+        let mut buf = thread::spawn(move || {
+            buf.copy_from_slice(b"abcd");
+            buf
+        })
+        .join()
+        .unwrap();
+        buf[0] = b'A';
+
+        let shared = buf.share();
+        assert_eq!(shared.as_ptr(), addr);
+        let peer = shared.clone();
+        let shared = LazyRc::try_into_unique(shared).unwrap_err();
+        drop(peer);
+
+        let unique = LazyRc::try_into_unique(shared).unwrap();
+        assert_eq!(&*unique, b"Abcd");
+        assert_eq!(unique.as_ptr(), addr);
+    }
+}
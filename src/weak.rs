@@ -0,0 +1,137 @@
+use core::cell::Cell;
+use core::fmt::{self, Debug};
+use core::ptr::NonNull;
+
+use crate::{increment, Counter, LazyRc};
+
+/// A non-owning reference to the value behind a [`LazyRc`].
+///
+/// Created with [`LazyRc::downgrade`]. The value is dropped once the last `LazyRc` goes away, but
+/// the counter block stays alive until the last `LazyWeak` is dropped.
+pub struct LazyWeak<T: ?Sized> {
+    pub(crate) data: NonNull<T>,
+    pub(crate) counter: NonNull<Counter>,
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + core::marker::Unsize<U>, U: ?Sized> core::ops::CoerceUnsized<LazyWeak<U>>
+    for LazyWeak<T>
+{
+}
+
+impl<T: ?Sized> LazyWeak<T> {
+    /// Attempts to upgrade to a [`LazyRc`], returning `None` if the value has already been dropped.
+    pub fn upgrade(&self) -> Option<LazyRc<T>> {
+        let counter = self.counter();
+        let strong = counter.strong.get();
+        if strong == 0 {
+            return None;
+        }
+        increment(&counter.strong);
+        Some(LazyRc {
+            data: self.data,
+            share_count: Cell::new(self.counter.as_ptr()),
+        })
+    }
+
+    /// Gets the number of [`LazyRc`] pointers to this allocation.
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.counter().strong.get()
+    }
+
+    /// Gets the number of `LazyWeak` pointers to this allocation, or zero if no strong pointers
+    /// remain.
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        let counter = self.counter();
+        if counter.strong.get() > 0 {
+            counter.weak.get() - 1
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    fn counter(&self) -> &Counter {
+        unsafe { self.counter.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Clone for LazyWeak<T> {
+    fn clone(&self) -> Self {
+        let counter = self.counter();
+        increment(&counter.weak);
+        Self {
+            data: self.data,
+            counter: self.counter,
+        }
+    }
+}
+
+impl<T: ?Sized> Debug for LazyWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(LazyWeak)")
+    }
+}
+
+impl<T: ?Sized> Drop for LazyWeak<T> {
+    fn drop(&mut self) {
+        unsafe { release(self.counter.as_ptr()) }
+    }
+}
+
+/// Drops one weak reference, freeing the counter block if it was the last.
+///
+/// # Safety
+///
+/// `counter` must point to a live counter block and the caller must own one weak reference.
+pub(crate) unsafe fn release(counter: *const Counter) {
+    let counter_ref = &*counter;
+    let weak = counter_ref.weak.get() - 1;
+    counter_ref.weak.set(weak);
+    if weak == 0 {
+        Counter::free(counter);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::prelude::rust_2021::*;
+
+    use super::*;
+
+    #[test]
+    fn test_weak() {
+        let strong = LazyRc::new(Box::new(String::from("cached")));
+        let weak = LazyRc::downgrade(&strong);
+        assert_eq!(LazyRc::strong_count(&strong), 1);
+        assert_eq!(LazyRc::weak_count(&strong), 1);
+
+        let weak2 = weak.clone();
+        let strong2 = weak2.upgrade().unwrap();
+        assert_eq!(*strong2, "cached");
+        assert_eq!(weak.strong_count(), 2);
+        assert_eq!(weak.weak_count(), 2);
+
+        drop(strong);
+        drop(strong2);
+        assert!(weak.upgrade().is_none());
+        assert_eq!(weak.strong_count(), 0);
+        assert_eq!(weak2.weak_count(), 0);
+
+        drop(weak);
+        drop(weak2);
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_coerce_unsized() {
+        let strong = LazyRc::new(Box::new([1u8, 2]));
+        let weak = LazyRc::downgrade(&strong);
+        let weak: LazyWeak<[u8]> = weak;
+        assert_eq!(*weak.upgrade().unwrap(), [1, 2]);
+        drop(strong);
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Loom models of `LazyArc`'s lazily installed counter.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --test loom --release`.
+#![cfg(loom)]
+
+use loom::sync::atomic::{AtomicUsize, Ordering};
+use loom::sync::Arc;
+use loom::thread;
+
+use lazyrc::LazyArc;
+
+struct DropCount(Arc<AtomicUsize>);
+
+impl Drop for DropCount {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Two threads race to install the counter on the same handle.
+#[test]
+fn racing_first_clone() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let shared = Arc::new(LazyArc::new(Box::new(DropCount(drops.clone()))));
+
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || drop(LazyArc::clone(&shared)))
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(shared);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+/// The last owner frees the value, whichever thread it ends up on.
+#[test]
+fn concurrent_last_drop() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let first = LazyArc::new(Box::new(DropCount(drops.clone())));
+        let second = first.clone();
+
+        let worker = thread::spawn(move || drop(second));
+        drop(first);
+        worker.join().unwrap();
+
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+/// A clone made while another thread drops its handle keeps the value alive.
+#[test]
+fn clone_while_dropping() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let first = LazyArc::new(Box::new(DropCount(drops.clone())));
+        let second = first.clone();
+
+        let worker = thread::spawn(move || {
+            let third = second.clone();
+            drop(second);
+            third
+        });
+        drop(first);
+        let third = worker.join().unwrap();
+
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(third);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
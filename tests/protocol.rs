@@ -0,0 +1,278 @@
+//! Exercises the unsafe counter protocol across payload shapes.
+//!
+//! These tests are meant to be run under Miri with both aliasing models:
+//!
+//! ```sh
+//! cargo +nightly miri test
+//! MIRIFLAGS="-Zmiri-tree-borrows" cargo +nightly miri test
+//! ```
+#![cfg(not(loom))]
+
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+use lazyrc::{unsize_lazyrc, LazyArc, LazyRc, LazyWeak, MetaLazyRc, ThinLazyRc};
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Resets the drop count for the current thread, in case tests share one.
+fn reset() {
+    DROPS.with(|d| d.set(0));
+}
+
+fn drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+/// Counts drops on the current thread.
+///
+/// Not zero-sized, so boxing it allocates and Miri's leak check covers freeing the box.
+struct Tracked(#[allow(dead_code)] [u64; 4]);
+
+impl Tracked {
+    fn new() -> Self {
+        Tracked([0; 4])
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.set(d.get() + 1));
+    }
+}
+
+/// A zero-sized type with a drop side effect.
+struct Zst;
+
+impl Drop for Zst {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.set(d.get() + 1));
+    }
+}
+
+struct PanicOnDrop(#[allow(dead_code)] Tracked);
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        panic!("payload drop panicked");
+    }
+}
+
+#[test]
+fn str_payload() {
+    let text: LazyRc<str> = LazyRc::from("shared text");
+    let weak = LazyRc::downgrade(&text);
+    let peer = text.clone();
+    assert_eq!(&*weak.upgrade().unwrap(), "shared text");
+
+    let mut text = text;
+    LazyRc::make_mut(&mut text).make_ascii_uppercase();
+    assert_eq!(&*text, "SHARED TEXT");
+    assert_eq!(&*peer, "shared text");
+
+    drop(peer);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(String::try_from(text).unwrap(), "SHARED TEXT");
+}
+
+#[test]
+fn slice_payload() {
+    reset();
+    let values: LazyRc<[Tracked]> = (0..4).map(|_| Tracked::new()).collect();
+    let peers: Vec<_> = (0..3).map(|_| values.clone()).collect();
+    let sub = LazyRc::slice(&values, 1..3);
+    assert_eq!(sub.len(), 2);
+
+    drop(values);
+    drop(peers);
+    assert_eq!(drops(), 0);
+    drop(sub);
+    assert_eq!(drops(), 4);
+
+    let empty: LazyRc<[u64]> = LazyRc::from(Vec::new());
+    let peer = empty.clone();
+    assert!(peer.is_empty());
+    assert!(LazyRc::try_into_box(empty).is_err());
+}
+
+#[test]
+fn dyn_payload() {
+    reset();
+    let display = unsize_lazyrc!(LazyRc::new(Box::new(42u64)), dyn Display);
+    let weak: LazyWeak<dyn Display> = LazyRc::downgrade(&display);
+    let peer = display.clone();
+    assert_eq!(weak.upgrade().unwrap().to_string(), "42");
+    drop(display);
+    drop(peer);
+    assert!(weak.upgrade().is_none());
+
+    let any: LazyRc<dyn Any> = unsize_lazyrc!(LazyRc::new(Box::new(Tracked::new())), dyn Any);
+    let peer = any.clone();
+    let concrete = any.downcast::<Tracked>().ok().unwrap();
+    drop(peer);
+    assert_eq!(drops(), 0);
+    drop(concrete);
+    assert_eq!(drops(), 1);
+}
+
+#[test]
+fn zero_sized_payloads() {
+    reset();
+    let unit = LazyRc::new(Box::new(Zst));
+    let peer = unit.clone();
+    let weak = LazyRc::downgrade(&peer);
+    drop(unit);
+    drop(peer);
+    assert_eq!(drops(), 1);
+    assert!(weak.upgrade().is_none());
+
+    let slice: LazyRc<[Zst]> = (0..3).map(|_| Zst).collect();
+    let peer = slice.clone();
+    drop(slice);
+    let boxed = LazyRc::try_into_box(peer).ok().unwrap();
+    assert_eq!(drops(), 1);
+    drop(boxed);
+    assert_eq!(drops(), 4);
+
+    let empty: LazyRc<[Zst]> = LazyRc::from(Vec::new());
+    drop(empty.clone());
+}
+
+#[test]
+fn panicking_drop_unshared() {
+    reset();
+    let value = LazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(value)));
+    assert!(result.is_err());
+    // The fields are still dropped while unwinding, and the box is freed.
+    assert_eq!(drops(), 1);
+}
+
+#[test]
+fn panicking_drop_of_peer_is_deferred() {
+    reset();
+    let value = LazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    let peer = value.clone();
+    // Dropping a non-last handle never runs the payload's destructor.
+    drop(value);
+    assert_eq!(drops(), 0);
+    let boxed = LazyRc::try_into_box(peer).ok().unwrap();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(boxed)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 1);
+}
+
+#[test]
+fn panicking_drop_of_last_peer() {
+    reset();
+    let value = LazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    let weak = LazyRc::downgrade(&value);
+    let peer = value.clone();
+    drop(value);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(peer)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 1);
+    // The strong owners' share of the counter block was released before the payload panicked.
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.weak_count(), 0);
+    drop(weak);
+
+    // Without any weak pointers, the unwinding drop frees the counter block itself; Miri's leak
+    // check catches it if not.
+    let value = LazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    drop(value.clone());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(value)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 2);
+}
+
+#[test]
+fn panicking_drop_of_thin() {
+    reset();
+    let value = ThinLazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(value)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 1);
+
+    // The shared block is freed before the value's destructor runs.
+    let value = ThinLazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    drop(value.clone());
+    let peer = value.clone();
+    drop(value);
+    assert_eq!(drops(), 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(peer)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 2);
+}
+
+#[test]
+fn panicking_drop_of_meta() {
+    reset();
+    // A panicking value still frees the counter block and drops the metadata.
+    let value: MetaLazyRc<PanicOnDrop, Tracked> =
+        MetaLazyRc::new(Box::new(PanicOnDrop(Tracked::new())));
+    MetaLazyRc::meta_or_init(&value, |_| Tracked::new());
+    let peer = value.clone();
+    drop(value);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(peer)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 2);
+
+    // And panicking metadata still frees the value.
+    let value: MetaLazyRc<Tracked, PanicOnDrop> = MetaLazyRc::new(Box::new(Tracked::new()));
+    MetaLazyRc::meta_or_init(&value, |_| PanicOnDrop(Tracked::new()));
+    drop(value.clone());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| drop(value)));
+    assert!(result.is_err());
+    assert_eq!(drops(), 4);
+}
+
+#[test]
+fn raw_round_trip() {
+    reset();
+    let (ptr, mut counter) = LazyRc::into_raw(LazyRc::new(Box::new(Tracked::new())));
+    unsafe {
+        LazyRc::increment_strong_count(ptr, &mut counter);
+        LazyRc::increment_strong_count(ptr, &mut counter);
+        LazyRc::decrement_strong_count(ptr, counter);
+        LazyRc::decrement_strong_count(ptr, counter);
+        assert_eq!(drops(), 0);
+        LazyRc::decrement_strong_count(ptr, counter);
+    }
+    assert_eq!(drops(), 1);
+}
+
+#[test]
+fn weak_outlives_value() {
+    reset();
+    let weak = {
+        let strong = LazyRc::new(Box::new(Tracked::new()));
+        let weak = LazyRc::downgrade(&strong);
+        let _peer = strong.clone();
+        weak
+    };
+    assert_eq!(drops(), 1);
+    let weak2 = weak.clone();
+    drop(weak);
+    assert!(weak2.upgrade().is_none());
+}
+
+#[test]
+fn arc_cross_thread() {
+    let value: LazyArc<[u8]> = LazyArc::from(vec![1, 2, 3]);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let clone = value.clone();
+                assert_eq!(*clone, [1, 2, 3]);
+            });
+        }
+    });
+    let moved = value.clone();
+    thread::spawn(move || drop(moved)).join().unwrap();
+}